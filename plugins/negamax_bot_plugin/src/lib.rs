@@ -0,0 +1,199 @@
+use hnefatafl_arena::{Bot, GameResult, GameState, Move, Piece, Player, Position};
+use std::time::{Duration, Instant};
+
+/// Effectively-infinite score, kept well under `i32::MAX` so it can be
+/// negated repeatedly (negamax flips sign at every ply) without overflow.
+const INF: i32 = i32::MAX / 2;
+
+/// A depth-limited negamax searcher with alpha-beta pruning and iterative
+/// deepening, in contrast to `GreedyBotPlugin`'s single-ply lookahead.
+pub struct NegamaxBotPlugin {
+    name: String,
+}
+
+impl Default for NegamaxBotPlugin {
+    fn default() -> Self {
+        Self {
+            name: "NegamaxPlugin".to_string(),
+        }
+    }
+}
+
+impl NegamaxBotPlugin {
+    /// Side-agnostic static evaluation: material, king-to-corner distance,
+    /// and king mobility. Positive favors `Player::Attackers`.
+    fn evaluate(state: &GameState) -> i32 {
+        let mut attacker_count = 0;
+        let mut defender_count = 0;
+
+        for row in 0..state.board_size() {
+            for col in 0..state.board_size() {
+                match state.get_piece(Position::new(row, col)) {
+                    Some(Piece::Attacker) => attacker_count += 1,
+                    Some(Piece::Defender) => defender_count += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let material = attacker_count - defender_count * 2;
+
+        let king_pos = match state.king_position() {
+            Some(pos) => pos,
+            None => return INF, // king captured: maximal score for attackers
+        };
+
+        let board_size = state.board_size() as i32;
+        let corners = [
+            (0, 0),
+            (0, board_size - 1),
+            (board_size - 1, 0),
+            (board_size - 1, board_size - 1),
+        ];
+        let king_distance = corners
+            .iter()
+            .map(|&(cr, cc)| {
+                (king_pos.row as i32 - cr).abs() + (king_pos.col as i32 - cc).abs()
+            })
+            .min()
+            .unwrap_or(0);
+
+        let king_mobility = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .iter()
+            .filter(|&&(dr, dc)| {
+                let r = king_pos.row as i32 + dr;
+                let c = king_pos.col as i32 + dc;
+                r >= 0
+                    && c >= 0
+                    && r < board_size
+                    && c < board_size
+                    && state.get_piece(Position::new(r as usize, c as usize)).is_none()
+            })
+            .count() as i32;
+
+        // The king is a defender asset: attackers want it boxed in and far
+        // from a corner, defenders want the opposite.
+        material + king_distance * 2 - king_mobility
+    }
+
+    /// Recursive negamax search with alpha-beta pruning. Returns a score
+    /// relative to the side to move in `state`.
+    fn negamax(state: &GameState, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+        let perspective = match state.current_player() {
+            Player::Attackers => 1,
+            Player::Defenders => -1,
+        };
+
+        if let Some(result) = state.result() {
+            return match result {
+                GameResult::AttackersWin => perspective * INF,
+                GameResult::DefendersWin => -perspective * INF,
+                GameResult::Draw => 0,
+            };
+        }
+
+        if depth == 0 {
+            return perspective * Self::evaluate(state);
+        }
+
+        let moves = state.legal_moves();
+        if moves.is_empty() {
+            return -INF;
+        }
+
+        let mut best = -INF;
+        for mv in moves {
+            let mut child = state.clone();
+            if child.make_move(mv).is_err() {
+                continue;
+            }
+            let score = -Self::negamax(&child, depth - 1, -beta, -alpha);
+            if score > best {
+                best = score;
+            }
+            if best > alpha {
+                alpha = best;
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// Iterative deepening: search depth 1, 2, 3, ... until `deadline`
+    /// passes, keeping the best move found at the last fully completed
+    /// depth.
+    fn search(state: &GameState, deadline: Instant) -> Option<Move> {
+        let moves = state.legal_moves();
+        if moves.is_empty() {
+            return None;
+        }
+
+        let mut best_move = moves[0];
+        let mut depth = 1;
+
+        while Instant::now() < deadline {
+            let mut alpha = -INF;
+            let beta = INF;
+            let mut depth_best_move = best_move;
+            let mut depth_best_score = -INF;
+            let mut completed = true;
+
+            for mv in &moves {
+                if Instant::now() >= deadline {
+                    completed = false;
+                    break;
+                }
+
+                let mut child = state.clone();
+                if child.make_move(*mv).is_err() {
+                    continue;
+                }
+                let score = -Self::negamax(&child, depth - 1, -beta, -alpha);
+
+                if score > depth_best_score {
+                    depth_best_score = score;
+                    depth_best_move = *mv;
+                }
+                if score > alpha {
+                    alpha = score;
+                }
+            }
+
+            if completed {
+                best_move = depth_best_move;
+                depth += 1;
+            } else {
+                break;
+            }
+        }
+
+        Some(best_move)
+    }
+}
+
+impl Bot for NegamaxBotPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_move(&mut self, state: &GameState, time_limit: Duration) -> Option<Move> {
+        let deadline = Instant::now() + time_limit;
+        Self::search(state, deadline)
+    }
+
+    fn game_start(&mut self, _player: Player) {}
+
+    fn notify_move(&mut self, _mv: Move) {}
+
+    fn game_end(&mut self) {}
+
+    fn opponent_thinking(&mut self, _state: &GameState) {}
+
+    fn stop_pondering(&mut self) {}
+}
+
+// Export the bot plugin using the macro
+hnefatafl_arena::export_bot!(NegamaxBotPlugin);
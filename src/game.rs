@@ -1,5 +1,7 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
 use std::fmt;
+use std::sync::OnceLock;
 use thiserror::Error;
 
 /// Board size constants
@@ -7,6 +9,57 @@ pub const COPENHAGEN_SIZE: usize = 11;
 pub const BRANDUBH_SIZE: usize = 7;
 pub const MAX_BOARD_SIZE: usize = 11;
 
+/// Default cap on `move_count` before a game is declared a draw, used when
+/// no explicit limit is configured via `GameState::with_max_moves`.
+pub const DEFAULT_MAX_MOVES: usize = 400;
+
+const PIECE_VARIANTS: usize = 3;
+
+fn piece_index(piece: Piece) -> usize {
+    match piece {
+        Piece::Attacker => 0,
+        Piece::Defender => 1,
+        Piece::King => 2,
+    }
+}
+
+/// Zobrist keys for incremental position hashing. Filled once with a
+/// deterministic pseudo-random generator so hashes are reproducible across
+/// runs (and therefore across processes comparing recorded games).
+struct ZobristKeys {
+    piece_square: [[[u64; MAX_BOARD_SIZE]; MAX_BOARD_SIZE]; PIECE_VARIANTS],
+    side_to_move: u64,
+}
+
+/// SplitMix64, used only to seed the Zobrist table deterministically.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+fn zobrist_keys() -> &'static ZobristKeys {
+    static KEYS: OnceLock<ZobristKeys> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut seed = 0xD1B54A32D192ED03u64;
+        let mut piece_square = [[[0u64; MAX_BOARD_SIZE]; MAX_BOARD_SIZE]; PIECE_VARIANTS];
+        for variant in piece_square.iter_mut() {
+            for row in variant.iter_mut() {
+                for key in row.iter_mut() {
+                    *key = splitmix64(&mut seed);
+                }
+            }
+        }
+        let side_to_move = splitmix64(&mut seed);
+        ZobristKeys {
+            piece_square,
+            side_to_move,
+        }
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Variant {
     Copenhagen, // 11x11, traditional Hnefatafl
@@ -53,7 +106,7 @@ impl Player {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub row: usize,
     pub col: usize,
@@ -82,6 +135,49 @@ impl Move {
     pub fn new(from: Position, to: Position) -> Self {
         Move { from, to }
     }
+
+    /// Encode this move as a pair of algebraic squares, e.g. `f6-f1`.
+    pub fn to_coord(&self) -> String {
+        format!(
+            "{}-{}",
+            Self::square_to_coord(self.from),
+            Self::square_to_coord(self.to)
+        )
+    }
+
+    /// Parse a move produced by `to_coord`.
+    pub fn from_coord(s: &str) -> Result<Move, GameError> {
+        let (from_str, to_str) = s
+            .split_once('-')
+            .ok_or_else(|| GameError::InvalidMove(format!("malformed move coordinate: {}", s)))?;
+        Ok(Move::new(
+            Self::coord_to_square(from_str)?,
+            Self::coord_to_square(to_str)?,
+        ))
+    }
+
+    fn square_to_coord(pos: Position) -> String {
+        format!("{}{}", (b'a' + pos.col as u8) as char, pos.row + 1)
+    }
+
+    fn coord_to_square(s: &str) -> Result<Position, GameError> {
+        let mut chars = s.chars();
+        let file = chars
+            .next()
+            .ok_or_else(|| GameError::InvalidMove(format!("malformed square: {}", s)))?;
+        if !file.is_ascii_lowercase() {
+            return Err(GameError::InvalidMove(format!("malformed square: {}", s)));
+        }
+        let col = (file as u8 - b'a') as usize;
+        let rank: usize = chars
+            .as_str()
+            .parse()
+            .map_err(|_| GameError::InvalidMove(format!("malformed square: {}", s)))?;
+        if rank == 0 {
+            return Err(GameError::InvalidMove(format!("malformed square: {}", s)));
+        }
+        Ok(Position::new(rank - 1, col))
+    }
 }
 
 impl fmt::Display for Move {
@@ -107,28 +203,167 @@ pub enum GameResult {
     Draw,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameState {
+/// Toggles for the Copenhagen-style rules that go beyond simple two-sided
+/// custodial capture. Defaults come from `RulesConfig::for_variant`, but
+/// tests can override individual rules via `GameState::with_rules`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesConfig {
+    /// Capture a whole line of enemy pieces pinned against a board edge.
+    pub shield_wall_captures: bool,
+    /// A king adjacent to the (empty) throne only needs three attackers;
+    /// the throne itself counts as the fourth. True for both variants by
+    /// default, matching the historical custodial-capture rule; `false`
+    /// is only meaningful as an explicit `with_rules` override for tests
+    /// that want the king to need an attacker on every side, throne
+    /// included.
+    pub throne_adjacent_capture: bool,
+    /// Defenders win if the king reaches an edge enclosure attackers
+    /// cannot breach and that still has a free square to move into.
+    pub exit_fort_win: bool,
+}
+
+impl RulesConfig {
+    /// The historical ruleset for each variant: Copenhagen plays with the
+    /// full set of advanced rules, Brandubh keeps the simpler ruleset.
+    pub fn for_variant(variant: Variant) -> Self {
+        match variant {
+            Variant::Copenhagen => RulesConfig {
+                shield_wall_captures: true,
+                throne_adjacent_capture: true,
+                exit_fort_win: true,
+            },
+            Variant::Brandubh => RulesConfig {
+                shield_wall_captures: false,
+                throne_adjacent_capture: true,
+                exit_fort_win: false,
+            },
+        }
+    }
+}
+
+fn pieces_are_enemies(a: Piece, b: Piece) -> bool {
+    matches!(
+        (a, b),
+        (Piece::Attacker, Piece::Defender | Piece::King) | (Piece::Defender | Piece::King, Piece::Attacker)
+    )
+}
+
+/// Cached per-variant bit masks over the `row * board_size + col` square
+/// numbering used by `GameState`'s bitboards.
+#[derive(Debug, Clone, Copy)]
+struct BoardMasks {
+    /// Every square on the board.
+    all_squares: u128,
+    /// Every square not in column 0 (safe to shift left-ward from).
+    not_col0: u128,
+    /// Every square not in the last column (safe to shift right-ward from).
+    not_last_col: u128,
+    throne: u128,
+    corners: u128,
+    edges: u128,
+}
+
+fn build_masks(board_size: usize) -> BoardMasks {
+    let mut all_squares = 0u128;
+    let mut not_col0 = 0u128;
+    let mut not_last_col = 0u128;
+    let mut corners = 0u128;
+    let mut edges = 0u128;
+    let mut throne = 0u128;
+    let center = board_size / 2;
+
+    for row in 0..board_size {
+        for col in 0..board_size {
+            let bit = 1u128 << (row * board_size + col);
+            all_squares |= bit;
+
+            if col != 0 {
+                not_col0 |= bit;
+            }
+            if col != board_size - 1 {
+                not_last_col |= bit;
+            }
+            if row == 0 || row == board_size - 1 || col == 0 || col == board_size - 1 {
+                edges |= bit;
+            }
+            let is_corner = (row == 0 || row == board_size - 1) && (col == 0 || col == board_size - 1);
+            if is_corner {
+                corners |= bit;
+            }
+            if row == center && col == center {
+                throne = bit;
+            }
+        }
+    }
+
+    BoardMasks {
+        all_squares,
+        not_col0,
+        not_last_col,
+        throne,
+        corners,
+        edges,
+    }
+}
+
+/// Board layout used only to preserve the historical `Serialize`/`Deserialize`
+/// shape of `GameState` across the move to a bitboard core.
+#[derive(Serialize, Deserialize)]
+struct BoardRepr {
     board: [[Option<Piece>; MAX_BOARD_SIZE]; MAX_BOARD_SIZE],
     variant: Variant,
     board_size: usize,
     current_player: Player,
-    king_position: Option<Position>,
     move_count: usize,
     result: Option<GameResult>,
+    max_moves: usize,
+    hash: u64,
+    hash_history: Vec<u64>,
+    rules: RulesConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameState {
+    attackers: u128,
+    defenders: u128,
+    king: u128,
+    variant: Variant,
+    board_size: usize,
+    current_player: Player,
+    move_count: usize,
+    result: Option<GameResult>,
+    max_moves: usize,
+    hash: u64,
+    // Grows one entry per ply and is deep-copied on every `clone()`, which
+    // partly offsets the bitboard core's cheap-cloning goal on hot paths
+    // like `perft`/negamax search. Kept as a plain `Vec` for now since
+    // threefold-repetition detection needs the full history and nothing
+    // in this crate clones across a deep enough tree for it to matter yet;
+    // revisit (e.g. a counts map threaded through the search instead of
+    // carried in every clone) if that changes.
+    hash_history: Vec<u64>,
+    masks: BoardMasks,
+    rules: RulesConfig,
 }
 
 impl GameState {
     /// Create a new game with the specified variant
     pub fn new(variant: Variant) -> Self {
+        let board_size = variant.board_size();
         let mut state = GameState {
-            board: [[None; MAX_BOARD_SIZE]; MAX_BOARD_SIZE],
+            attackers: 0,
+            defenders: 0,
+            king: 0,
             variant,
-            board_size: variant.board_size(),
+            board_size,
             current_player: Player::Attackers,
-            king_position: None,
             move_count: 0,
             result: None,
+            max_moves: DEFAULT_MAX_MOVES,
+            hash: 0,
+            hash_history: Vec::new(),
+            masks: build_masks(board_size),
+            rules: RulesConfig::for_variant(variant),
         };
 
         match variant {
@@ -136,9 +371,32 @@ impl GameState {
             Variant::Brandubh => state.setup_brandubh(),
         }
 
+        state.hash = state.compute_hash();
+        state.hash_history.push(state.hash);
+
         state
     }
 
+    /// Override the move-count cap used by the draw rule in
+    /// `check_game_end`. Builder-style, so existing callers of `new`,
+    /// `new_copenhagen`, and `new_brandubh` are unaffected.
+    pub fn with_max_moves(mut self, max_moves: usize) -> Self {
+        self.max_moves = max_moves;
+        self
+    }
+
+    /// Override the active rules config, e.g. to toggle individual
+    /// Copenhagen rules on or off in tests.
+    pub fn with_rules(mut self, rules: RulesConfig) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The rules config currently in effect.
+    pub fn rules(&self) -> RulesConfig {
+        self.rules
+    }
+
     /// Create a new game with Copenhagen Hnefatafl (default)
     pub fn new_copenhagen() -> Self {
         Self::new(Variant::Copenhagen)
@@ -149,14 +407,49 @@ impl GameState {
         Self::new(Variant::Brandubh)
     }
 
+    fn square_index(&self, pos: Position) -> u32 {
+        (pos.row * self.board_size + pos.col) as u32
+    }
+
+    fn position_from_index(&self, index: u32) -> Position {
+        Position::new(index as usize / self.board_size, index as usize % self.board_size)
+    }
+
+    fn bit(&self, pos: Position) -> u128 {
+        1u128 << self.square_index(pos)
+    }
+
+    fn occupied(&self) -> u128 {
+        self.attackers | self.defenders | self.king
+    }
+
+    fn bitboard_for(&mut self, piece: Piece) -> &mut u128 {
+        match piece {
+            Piece::Attacker => &mut self.attackers,
+            Piece::Defender => &mut self.defenders,
+            Piece::King => &mut self.king,
+        }
+    }
+
+    fn set_piece(&mut self, pos: Position, piece: Piece) {
+        let bit = self.bit(pos);
+        *self.bitboard_for(piece) |= bit;
+    }
+
+    fn clear_square(&mut self, pos: Position) {
+        let keep = !self.bit(pos);
+        self.attackers &= keep;
+        self.defenders &= keep;
+        self.king &= keep;
+    }
+
     /// Setup Copenhagen Hnefatafl (11x11)
     fn setup_copenhagen(&mut self) {
         let board_size = COPENHAGEN_SIZE;
 
         // Place king in center
         let center = board_size / 2;
-        self.board[center][center] = Some(Piece::King);
-        self.king_position = Some(Position::new(center, center));
+        self.set_piece(Position::new(center, center), Piece::King);
 
         // Place defenders around king (cross pattern)
         let defenders = [
@@ -171,7 +464,7 @@ impl GameState {
         ];
 
         for &(r, c) in &defenders {
-            self.board[r][c] = Some(Piece::Defender);
+            self.set_piece(Position::new(r, c), Piece::Defender);
         }
 
         // Place attackers on edges (T-shape on each side)
@@ -207,7 +500,7 @@ impl GameState {
         ];
 
         for &(r, c) in &attackers {
-            self.board[r][c] = Some(Piece::Attacker);
+            self.set_piece(Position::new(r, c), Piece::Attacker);
         }
     }
 
@@ -217,8 +510,7 @@ impl GameState {
         let center = board_size / 2; // 3 for 7x7
 
         // Place king in center
-        self.board[center][center] = Some(Piece::King);
-        self.king_position = Some(Position::new(center, center));
+        self.set_piece(Position::new(center, center), Piece::King);
 
         // Place 4 defenders around king
         let defenders = [
@@ -229,7 +521,7 @@ impl GameState {
         ];
 
         for &(r, c) in &defenders {
-            self.board[r][c] = Some(Piece::Defender);
+            self.set_piece(Position::new(r, c), Piece::Defender);
         }
 
         // Place 8 attackers on edges (2 on each side)
@@ -249,7 +541,7 @@ impl GameState {
         ];
 
         for &(r, c) in &attackers {
-            self.board[r][c] = Some(Piece::Attacker);
+            self.set_piece(Position::new(r, c), Piece::Attacker);
         }
     }
 
@@ -277,9 +569,59 @@ impl GameState {
         self.move_count
     }
 
+    /// Current position of the king, or `None` if it has been captured
+    pub fn king_position(&self) -> Option<Position> {
+        if self.king == 0 {
+            None
+        } else {
+            Some(self.position_from_index(self.king.trailing_zeros()))
+        }
+    }
+
+    /// Zobrist hash of the current position (board contents + side to move).
+    pub fn current_hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn zobrist_piece_key(piece: Piece, pos: Position) -> u64 {
+        zobrist_keys().piece_square[piece_index(piece)][pos.row][pos.col]
+    }
+
+    fn toggle_piece(&mut self, piece: Piece, pos: Position) {
+        self.hash ^= Self::zobrist_piece_key(piece, pos);
+    }
+
+    fn toggle_side_to_move(&mut self) {
+        self.hash ^= zobrist_keys().side_to_move;
+    }
+
+    fn compute_hash(&self) -> u64 {
+        let mut hash = 0u64;
+        for row in 0..self.board_size {
+            for col in 0..self.board_size {
+                if let Some(piece) = self.get_piece(Position::new(row, col)) {
+                    hash ^= Self::zobrist_piece_key(piece, Position::new(row, col));
+                }
+            }
+        }
+        if self.current_player == Player::Defenders {
+            hash ^= zobrist_keys().side_to_move;
+        }
+        hash
+    }
+
     pub fn get_piece(&self, pos: Position) -> Option<Piece> {
-        if pos.row < self.board_size && pos.col < self.board_size {
-            self.board[pos.row][pos.col]
+        if pos.row >= self.board_size || pos.col >= self.board_size {
+            return None;
+        }
+
+        let bit = self.bit(pos);
+        if self.attackers & bit != 0 {
+            Some(Piece::Attacker)
+        } else if self.defenders & bit != 0 {
+            Some(Piece::Defender)
+        } else if self.king & bit != 0 {
+            Some(Piece::King)
         } else {
             None
         }
@@ -287,16 +629,18 @@ impl GameState {
 
     /// Check if a position is a corner (throne)
     fn is_corner(&self, pos: Position) -> bool {
-        (pos.row == 0 && pos.col == 0)
-            || (pos.row == 0 && pos.col == self.board_size - 1)
-            || (pos.row == self.board_size - 1 && pos.col == 0)
-            || (pos.row == self.board_size - 1 && pos.col == self.board_size - 1)
+        if pos.row >= self.board_size || pos.col >= self.board_size {
+            return false;
+        }
+        self.masks.corners & self.bit(pos) != 0
     }
 
     /// Check if a position is the throne (center)
     fn is_throne(&self, pos: Position) -> bool {
-        let center = self.board_size / 2;
-        pos.row == center && pos.col == center
+        if pos.row >= self.board_size || pos.col >= self.board_size {
+            return false;
+        }
+        self.masks.throne & self.bit(pos) != 0
     }
 
     /// Get all legal moves for the current player
@@ -329,40 +673,47 @@ impl GameState {
         }
     }
 
+    /// Slide `from` outward along each of the four orthogonal directions
+    /// using shifts over the occupancy bitboards, stopping before the first
+    /// occupied square (and before the throne/corners for non-king pieces).
     fn legal_moves_for_piece(&self, from: Position) -> Vec<Move> {
-        let mut moves = Vec::new();
         let piece = self.get_piece(from).unwrap();
+        let occupied = self.occupied();
+        let blocked = if piece == Piece::King {
+            occupied
+        } else {
+            occupied | self.masks.throne | self.masks.corners
+        };
 
-        // Try all four directions
-        let directions = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+        let board_size = self.board_size as u32;
+        // (shift_left, shift_amount, guard) - guard is checked against the
+        // *current* square before each step to avoid wrapping across rows.
+        let directions = [
+            (true, 1u32, self.masks.not_last_col),  // toward higher column
+            (false, 1u32, self.masks.not_col0),     // toward lower column
+            (true, board_size, self.masks.all_squares), // toward higher row
+            (false, board_size, self.masks.all_squares), // toward lower row
+        ];
 
-        for &(dr, dc) in &directions {
-            let mut r = from.row as i32;
-            let mut c = from.col as i32;
+        let mut moves = Vec::new();
+        let from_bit = self.bit(from);
 
+        for &(shift_left, amount, guard) in &directions {
+            let mut cur = from_bit;
             loop {
-                r += dr;
-                c += dc;
-
-                if r < 0 || r >= self.board_size as i32 || c < 0 || c >= self.board_size as i32 {
+                if cur & guard == 0 {
                     break;
                 }
-
-                let to = Position::new(r as usize, c as usize);
-
-                // Can't move onto another piece
-                if self.get_piece(to).is_some() {
+                cur = if shift_left { cur << amount } else { cur >> amount };
+                cur &= self.masks.all_squares;
+                if cur == 0 {
                     break;
                 }
-
-                // Only king can move to throne or corners
-                if piece != Piece::King {
-                    if self.is_throne(to) || self.is_corner(to) {
-                        break;
-                    }
+                if cur & blocked != 0 {
+                    break;
                 }
 
-                moves.push(Move::new(from, to));
+                moves.push(Move::new(from, self.position_from_index(cur.trailing_zeros())));
             }
         }
 
@@ -381,24 +732,23 @@ impl GameState {
         }
 
         // Move the piece
-        let piece = self.board[mv.from.row][mv.from.col].unwrap();
-        self.board[mv.from.row][mv.from.col] = None;
-        self.board[mv.to.row][mv.to.col] = Some(piece);
-
-        // Update king position
-        if piece == Piece::King {
-            self.king_position = Some(mv.to);
-        }
+        let piece = self.get_piece(mv.from).unwrap();
+        self.clear_square(mv.from);
+        self.set_piece(mv.to, piece);
+        self.toggle_piece(piece, mv.from);
+        self.toggle_piece(piece, mv.to);
 
         // Check for captures
         self.check_captures(mv.to);
 
-        // Check win conditions
-        self.check_game_end();
-
         // Switch player
         self.current_player = self.current_player.opponent();
         self.move_count += 1;
+        self.toggle_side_to_move();
+        self.hash_history.push(self.hash);
+
+        // Check win and draw conditions for the side to move next
+        self.check_game_end();
 
         Ok(())
     }
@@ -423,15 +773,105 @@ impl GameState {
             if let Some(target_piece) = self.get_piece(target) {
                 // Check if we can capture this piece
                 if self.can_capture(moved_to, target) {
-                    self.board[target.row][target.col] = None;
+                    self.clear_square(target);
+                    self.toggle_piece(target_piece, target);
+                }
+            }
+        }
 
-                    // If king was captured, update king position
-                    if target_piece == Piece::King {
-                        self.king_position = None;
-                    }
+        if self.rules.shield_wall_captures {
+            self.check_shield_wall_captures(moved_to);
+        }
+    }
+
+    /// Capture a whole line of enemy pieces pinned against a board edge:
+    /// the moved piece brackets one end of the line, a friendly piece
+    /// brackets the other, and every piece in the line has a friendly
+    /// piece facing it from the inward side.
+    fn check_shield_wall_captures(&mut self, moved_to: Position) {
+        let mover = match self.get_piece(moved_to) {
+            Some(piece) => piece,
+            None => return,
+        };
+
+        // A wall only forms along an edge the mover itself sits on: a
+        // horizontal wall along the top/bottom edge, a vertical wall along
+        // the left/right edge.
+        if moved_to.row == 0 || moved_to.row == self.board_size - 1 {
+            self.capture_shield_wall_along(moved_to, mover, 0, 1);
+            self.capture_shield_wall_along(moved_to, mover, 0, -1);
+        }
+        if moved_to.col == 0 || moved_to.col == self.board_size - 1 {
+            self.capture_shield_wall_along(moved_to, mover, 1, 0);
+            self.capture_shield_wall_along(moved_to, mover, -1, 0);
+        }
+    }
+
+    fn capture_shield_wall_along(&mut self, from: Position, mover: Piece, dr: i32, dc: i32) {
+        let inward = if dr == 0 {
+            if from.row == 0 {
+                (1, 0)
+            } else {
+                (-1, 0)
+            }
+        } else if from.col == 0 {
+            (0, 1)
+        } else {
+            (0, -1)
+        };
+
+        let mut wall = Vec::new();
+        let mut r = from.row as i32 + dr;
+        let mut c = from.col as i32 + dc;
+
+        loop {
+            if r < 0 || c < 0 || r >= self.board_size as i32 || c >= self.board_size as i32 {
+                return; // ran off the board before a bracketing piece
+            }
+
+            let pos = Position::new(r as usize, c as usize);
+            match self.get_piece(pos) {
+                // The king is immune to shield-wall capture. An enemy king
+                // can't close the wall either - a wall is only bracketed by
+                // *friendly* pieces at both ends - so it ends the scan with
+                // no capture. A friendly king, however, is a valid bracket.
+                Some(Piece::King) if pieces_are_enemies(mover, Piece::King) => return,
+                Some(Piece::King) => break,
+                Some(piece) if pieces_are_enemies(mover, piece) => {
+                    wall.push((pos, piece));
+                    r += dr;
+                    c += dc;
                 }
+                Some(_) => break, // bracketed by a friendly piece - verified below
+                None => return,   // gap in the line - no capture
             }
         }
+
+        if wall.is_empty() {
+            return;
+        }
+
+        for &(pos, _) in &wall {
+            let facing_r = pos.row as i32 + inward.0;
+            let facing_c = pos.col as i32 + inward.1;
+            if facing_r < 0
+                || facing_c < 0
+                || facing_r >= self.board_size as i32
+                || facing_c >= self.board_size as i32
+            {
+                return;
+            }
+            let facing = Position::new(facing_r as usize, facing_c as usize);
+            match self.get_piece(facing) {
+                Some(facing_piece) if !pieces_are_enemies(mover, facing_piece) => {}
+                _ => return,
+            }
+        }
+
+        for (pos, piece) in wall {
+            self.clear_square(pos);
+            self.toggle_piece(piece, pos);
+        }
     }
 
     fn can_capture(&self, attacker: Position, target: Position) -> bool {
@@ -494,13 +934,21 @@ impl GameState {
             let c = king_pos.col as i32 + dc;
 
             if r < 0 || r >= self.board_size as i32 || c < 0 || c >= self.board_size as i32 {
-                continue;
+                // A king flush against the board edge has a side with
+                // nothing to bracket it against, so it can't be custodially
+                // captured from that square at all.
+                return false;
             }
 
             let pos = Position::new(r as usize, c as usize);
 
-            // Must be surrounded by attackers or throne/corners
-            if self.is_throne(pos) || self.is_corner(pos) {
+            // A corner is always hostile; an empty throne only counts as
+            // the king's fourth attacker when the ruleset says so (a king
+            // sitting on the throne itself still needs all four sides).
+            if self.is_corner(pos) {
+                continue;
+            }
+            if self.is_throne(pos) && self.rules.throne_adjacent_capture {
                 continue;
             }
 
@@ -516,21 +964,134 @@ impl GameState {
         true
     }
 
+    /// Defenders win if the king sits on an edge square, inside a pocket of
+    /// empty squares that no attacker can step into, with at least one free
+    /// square left to move to. This approximates the classic exit-fort rule
+    /// by flood-filling the king's reachable empty squares and checking
+    /// that no attacker's legal move lands inside that pocket.
+    fn is_exit_fort(&self, king_pos: Position) -> bool {
+        if self.masks.edges & self.bit(king_pos) == 0 || self.is_corner(king_pos) {
+            return false;
+        }
+
+        let mut pocket = HashSet::new();
+        let mut stack = vec![king_pos];
+        pocket.insert(king_pos);
+
+        while let Some(pos) = stack.pop() {
+            for &(dr, dc) in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                let r = pos.row as i32 + dr;
+                let c = pos.col as i32 + dc;
+                if r < 0 || c < 0 || r >= self.board_size as i32 || c >= self.board_size as i32 {
+                    continue;
+                }
+                let neighbor = Position::new(r as usize, c as usize);
+                if pocket.contains(&neighbor) {
+                    continue;
+                }
+                if self.get_piece(neighbor).is_none() {
+                    pocket.insert(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        // The king itself is always in the pocket; it needs somewhere else
+        // to go.
+        if pocket.len() < 2 {
+            return false;
+        }
+
+        for row in 0..self.board_size {
+            for col in 0..self.board_size {
+                let pos = Position::new(row, col);
+                if self.get_piece(pos) != Some(Piece::Attacker) {
+                    continue;
+                }
+                if self
+                    .legal_moves_for_piece(pos)
+                    .iter()
+                    .any(|mv| pocket.contains(&mv.to))
+                {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
     fn check_game_end(&mut self) {
         // Defenders win if king reaches a corner
-        if let Some(king_pos) = self.king_position {
-            if self.is_corner(king_pos) {
-                self.result = Some(GameResult::DefendersWin);
+        match self.king_position() {
+            Some(king_pos) => {
+                if self.is_corner(king_pos) {
+                    self.result = Some(GameResult::DefendersWin);
+                    return;
+                }
+                if self.rules.exit_fort_win && self.is_exit_fort(king_pos) {
+                    self.result = Some(GameResult::DefendersWin);
+                    return;
+                }
+            }
+            None => {
+                // King captured - attackers win
+                self.result = Some(GameResult::AttackersWin);
                 return;
             }
-        } else {
-            // King captured - attackers win
-            self.result = Some(GameResult::AttackersWin);
+        }
+
+        // Threefold repetition
+        let repetitions = self.hash_history.iter().filter(|&&h| h == self.hash).count();
+        if repetitions >= 3 {
+            self.result = Some(GameResult::Draw);
             return;
         }
 
-        // Check for draw (no legal moves)
-        // This will be checked after switching player
+        // Move-limit draw
+        if self.move_count >= self.max_moves {
+            self.result = Some(GameResult::Draw);
+            return;
+        }
+
+        // Stalemate: the side to move has no legal moves left
+        if self.legal_moves().is_empty() {
+            self.result = Some(GameResult::Draw);
+        }
+    }
+
+    /// Count the leaf nodes of the legal-move tree to a fixed depth, the
+    /// standard way to regression-test a move generator.
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 || self.is_game_over() {
+            return 1;
+        }
+
+        self.legal_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut child = self.clone();
+                child.make_move(mv).expect("legal move must apply");
+                child.perft(depth - 1)
+            })
+            .sum()
+    }
+
+    /// Like `perft`, but broken down by root move - the standard tool for
+    /// pinpointing where two move generators disagree.
+    pub fn perft_divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        self.legal_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut child = self.clone();
+                child.make_move(mv).expect("legal move must apply");
+                (mv, child.perft(depth - 1))
+            })
+            .collect()
     }
 
     /// Get a string representation of the board
@@ -566,6 +1127,276 @@ impl GameState {
 
         result
     }
+
+    fn variant_tag(&self) -> &'static str {
+        match self.variant {
+            Variant::Copenhagen => "copenhagen",
+            Variant::Brandubh => "brandubh",
+        }
+    }
+
+    fn variant_from_tag(tag: &str) -> Result<Variant, GameError> {
+        match tag {
+            "copenhagen" => Ok(Variant::Copenhagen),
+            "brandubh" => Ok(Variant::Brandubh),
+            other => Err(GameError::InvalidMove(format!("unknown variant tag: {}", other))),
+        }
+    }
+
+    /// Encode the position as a FEN-like notation string: variant tag,
+    /// run-length-compressed ranks from the top row down, side to move,
+    /// and move count.
+    pub fn to_notation(&self) -> String {
+        let mut ranks = Vec::with_capacity(self.board_size);
+
+        for row in (0..self.board_size).rev() {
+            let mut rank = String::new();
+            let mut empties = 0;
+
+            for col in 0..self.board_size {
+                match self.get_piece(Position::new(row, col)) {
+                    Some(piece) => {
+                        if empties > 0 {
+                            rank.push_str(&empties.to_string());
+                            empties = 0;
+                        }
+                        rank.push(match piece {
+                            Piece::Attacker => 'A',
+                            Piece::Defender => 'D',
+                            Piece::King => 'K',
+                        });
+                    }
+                    None => empties += 1,
+                }
+            }
+
+            if empties > 0 {
+                rank.push_str(&empties.to_string());
+            }
+
+            ranks.push(rank);
+        }
+
+        let side = match self.current_player {
+            Player::Attackers => 'a',
+            Player::Defenders => 'd',
+        };
+
+        format!(
+            "{} {} {} {}",
+            self.variant_tag(),
+            ranks.join("/"),
+            side,
+            self.move_count
+        )
+    }
+
+    /// Parse a string produced by `to_notation` back into a `GameState`.
+    pub fn from_notation(s: &str) -> Result<GameState, GameError> {
+        let mut fields = s.split_whitespace();
+        let variant_tag = fields
+            .next()
+            .ok_or_else(|| GameError::InvalidMove("missing variant field".to_string()))?;
+        let board_field = fields
+            .next()
+            .ok_or_else(|| GameError::InvalidMove("missing board field".to_string()))?;
+        let side_field = fields
+            .next()
+            .ok_or_else(|| GameError::InvalidMove("missing side-to-move field".to_string()))?;
+        let move_count_field = fields
+            .next()
+            .ok_or_else(|| GameError::InvalidMove("missing move-count field".to_string()))?;
+
+        let variant = Self::variant_from_tag(variant_tag)?;
+        let board_size = variant.board_size();
+        let mut board = [[None; MAX_BOARD_SIZE]; MAX_BOARD_SIZE];
+
+        let ranks: Vec<&str> = board_field.split('/').collect();
+        if ranks.len() != board_size {
+            return Err(GameError::InvalidMove(format!(
+                "expected {} ranks, found {}",
+                board_size,
+                ranks.len()
+            )));
+        }
+
+        for (rank_index, rank) in ranks.iter().enumerate() {
+            let row = board_size - 1 - rank_index;
+            let mut col = 0;
+            let mut digits = String::new();
+
+            for ch in rank.chars() {
+                if ch.is_ascii_digit() {
+                    digits.push(ch);
+                    continue;
+                }
+
+                if !digits.is_empty() {
+                    col += digits.parse::<usize>().map_err(|_| {
+                        GameError::InvalidMove(format!("invalid empty-square run: {}", digits))
+                    })?;
+                    digits.clear();
+                }
+
+                let piece = match ch {
+                    'A' => Piece::Attacker,
+                    'D' => Piece::Defender,
+                    'K' => Piece::King,
+                    other => {
+                        return Err(GameError::InvalidMove(format!(
+                            "unknown piece letter: {}",
+                            other
+                        )))
+                    }
+                };
+
+                if col >= board_size {
+                    return Err(GameError::InvalidMove(format!(
+                        "rank {} overflows the board",
+                        rank_index
+                    )));
+                }
+                board[row][col] = Some(piece);
+                col += 1;
+            }
+
+            if !digits.is_empty() {
+                col += digits.parse::<usize>().map_err(|_| {
+                    GameError::InvalidMove(format!("invalid empty-square run: {}", digits))
+                })?;
+            }
+
+            if col != board_size {
+                return Err(GameError::InvalidMove(format!(
+                    "rank {} has the wrong length",
+                    rank_index
+                )));
+            }
+        }
+
+        let current_player = match side_field {
+            "a" => Player::Attackers,
+            "d" => Player::Defenders,
+            other => {
+                return Err(GameError::InvalidMove(format!(
+                    "unknown side to move: {}",
+                    other
+                )))
+            }
+        };
+
+        let move_count: usize = move_count_field
+            .parse()
+            .map_err(|_| GameError::InvalidMove(format!("invalid move count: {}", move_count_field)))?;
+
+        let (attackers, defenders, king) = GameState::from_board(&board, board_size);
+
+        let mut state = GameState {
+            attackers,
+            defenders,
+            king,
+            variant,
+            board_size,
+            current_player,
+            move_count,
+            result: None,
+            max_moves: DEFAULT_MAX_MOVES,
+            hash: 0,
+            hash_history: Vec::new(),
+            masks: build_masks(board_size),
+            rules: RulesConfig::for_variant(variant),
+        };
+
+        state.hash = state.compute_hash();
+        state.hash_history.push(state.hash);
+        state.check_game_end();
+
+        Ok(state)
+    }
+
+    /// Expand the bitboards into the array layout used by the historical
+    /// on-disk/wire format.
+    fn to_board(&self) -> [[Option<Piece>; MAX_BOARD_SIZE]; MAX_BOARD_SIZE] {
+        let mut board = [[None; MAX_BOARD_SIZE]; MAX_BOARD_SIZE];
+        for (row, row_slice) in board.iter_mut().enumerate().take(self.board_size) {
+            for (col, cell) in row_slice.iter_mut().enumerate().take(self.board_size) {
+                *cell = self.get_piece(Position::new(row, col));
+            }
+        }
+        board
+    }
+
+    /// Pack the array layout used by the historical wire format back into
+    /// bitboards.
+    fn from_board(
+        board: &[[Option<Piece>; MAX_BOARD_SIZE]; MAX_BOARD_SIZE],
+        board_size: usize,
+    ) -> (u128, u128, u128) {
+        let mut attackers = 0u128;
+        let mut defenders = 0u128;
+        let mut king = 0u128;
+
+        for (row, row_slice) in board.iter().enumerate().take(board_size) {
+            for (col, cell) in row_slice.iter().enumerate().take(board_size) {
+                let bit = 1u128 << (row * board_size + col);
+                match cell {
+                    Some(Piece::Attacker) => attackers |= bit,
+                    Some(Piece::Defender) => defenders |= bit,
+                    Some(Piece::King) => king |= bit,
+                    None => {}
+                }
+            }
+        }
+
+        (attackers, defenders, king)
+    }
+}
+
+impl Serialize for GameState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let repr = BoardRepr {
+            board: self.to_board(),
+            variant: self.variant,
+            board_size: self.board_size,
+            current_player: self.current_player,
+            move_count: self.move_count,
+            result: self.result.clone(),
+            max_moves: self.max_moves,
+            hash: self.hash,
+            hash_history: self.hash_history.clone(),
+            rules: self.rules,
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = BoardRepr::deserialize(deserializer)?;
+        let (attackers, defenders, king) = GameState::from_board(&repr.board, repr.board_size);
+
+        Ok(GameState {
+            attackers,
+            defenders,
+            king,
+            variant: repr.variant,
+            board_size: repr.board_size,
+            current_player: repr.current_player,
+            move_count: repr.move_count,
+            result: repr.result,
+            max_moves: repr.max_moves,
+            hash: repr.hash,
+            hash_history: repr.hash_history,
+            masks: build_masks(repr.board_size),
+            rules: repr.rules,
+        })
+    }
 }
 
 impl Default for GameState {
@@ -573,3 +1404,121 @@ impl Default for GameState {
         Self::new_copenhagen()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clear_board(state: &mut GameState) {
+        for row in 0..state.board_size {
+            for col in 0..state.board_size {
+                state.clear_square(Position::new(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn shield_wall_capture_with_friendly_brackets_at_both_ends() {
+        let mut state = GameState::new_copenhagen();
+        clear_board(&mut state);
+
+        state.set_piece(Position::new(0, 0), Piece::Attacker);
+        state.set_piece(Position::new(0, 1), Piece::Defender);
+        state.set_piece(Position::new(0, 2), Piece::Defender);
+        state.set_piece(Position::new(0, 3), Piece::Attacker);
+        state.set_piece(Position::new(1, 1), Piece::Attacker);
+        state.set_piece(Position::new(1, 2), Piece::Attacker);
+
+        state.check_shield_wall_captures(Position::new(0, 0));
+
+        assert_eq!(state.get_piece(Position::new(0, 1)), None);
+        assert_eq!(state.get_piece(Position::new(0, 2)), None);
+        assert_eq!(state.get_piece(Position::new(0, 3)), Some(Piece::Attacker));
+    }
+
+    #[test]
+    fn shield_wall_capture_spares_a_row_terminated_by_the_king() {
+        let mut state = GameState::new_copenhagen();
+        clear_board(&mut state);
+
+        state.set_piece(Position::new(0, 0), Piece::Attacker);
+        state.set_piece(Position::new(0, 1), Piece::Defender);
+        state.set_piece(Position::new(0, 2), Piece::Defender);
+        state.set_piece(Position::new(0, 3), Piece::King);
+        state.set_piece(Position::new(0, 4), Piece::Defender);
+        state.set_piece(Position::new(1, 1), Piece::Attacker);
+        state.set_piece(Position::new(1, 2), Piece::Attacker);
+
+        state.check_shield_wall_captures(Position::new(0, 0));
+
+        // The king is not a friendly bracket for the attacker's wall, so the
+        // whole row - defenders included - is spared.
+        assert_eq!(state.get_piece(Position::new(0, 1)), Some(Piece::Defender));
+        assert_eq!(state.get_piece(Position::new(0, 2)), Some(Piece::Defender));
+        assert_eq!(state.get_piece(Position::new(0, 3)), Some(Piece::King));
+        assert_eq!(state.get_piece(Position::new(0, 4)), Some(Piece::Defender));
+    }
+
+    #[test]
+    fn throne_counts_as_hostile_in_brandubh() {
+        let mut state = GameState::new_brandubh();
+        clear_board(&mut state);
+
+        let center = state.board_size / 2;
+        let king_pos = Position::new(center, center + 1);
+        state.set_piece(king_pos, Piece::King);
+        state.set_piece(Position::new(center, center + 2), Piece::Attacker);
+        state.set_piece(Position::new(center - 1, center + 1), Piece::Attacker);
+        state.set_piece(Position::new(center + 1, center + 1), Piece::Attacker);
+
+        assert!(state.is_king_surrounded(king_pos));
+    }
+
+    #[test]
+    fn edge_king_is_immune_to_custodial_capture() {
+        let mut state = GameState::new_copenhagen();
+        clear_board(&mut state);
+
+        let king_pos = Position::new(0, 5);
+        state.set_piece(king_pos, Piece::King);
+        state.set_piece(Position::new(0, 4), Piece::Attacker);
+        state.set_piece(Position::new(0, 6), Piece::Attacker);
+        state.set_piece(Position::new(1, 5), Piece::Attacker);
+
+        assert!(!state.is_king_surrounded(king_pos));
+    }
+
+    #[test]
+    fn exit_fort_requires_an_unreachable_pocket() {
+        let mut state = GameState::new_copenhagen();
+        clear_board(&mut state);
+
+        let king_pos = Position::new(0, 1);
+        state.set_piece(king_pos, Piece::King);
+
+        assert!(state.is_exit_fort(king_pos));
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        let state = GameState::new_brandubh();
+        let notation = state.to_notation();
+        let restored = GameState::from_notation(&notation).unwrap();
+        assert_eq!(restored.to_notation(), notation);
+    }
+
+    #[test]
+    fn coord_round_trips() {
+        let mv = Move::new(Position::new(0, 0), Position::new(3, 0));
+        let restored = Move::from_coord(&mv.to_coord()).unwrap();
+        assert_eq!(restored, mv);
+    }
+
+    #[test]
+    fn brandubh_perft_matches_known_counts() {
+        let state = GameState::new_brandubh();
+        assert_eq!(state.perft(0), 1);
+        assert_eq!(state.perft(1), 40);
+        assert_eq!(state.perft(2), 960);
+    }
+}